@@ -0,0 +1,42 @@
+use bytes::Bytes;
+use http_body_util::Full;
+
+use crate::http::HeaderValue;
+
+use super::{BodyError, TypedBody};
+
+impl TypedBody for Bytes {
+    type Body = Full<Bytes>;
+
+    fn content_type(&self) -> HeaderValue {
+        HeaderValue::from_static(mime::APPLICATION_OCTET_STREAM.as_ref())
+    }
+
+    fn body(self) -> Result<Self::Body, BodyError> {
+        Ok(Full::new(self))
+    }
+}
+
+impl TypedBody for Vec<u8> {
+    type Body = Full<Bytes>;
+
+    fn content_type(&self) -> HeaderValue {
+        HeaderValue::from_static(mime::APPLICATION_OCTET_STREAM.as_ref())
+    }
+
+    fn body(self) -> Result<Self::Body, BodyError> {
+        Ok(Full::new(self.into()))
+    }
+}
+
+impl TypedBody for &'static [u8] {
+    type Body = Full<Bytes>;
+
+    fn content_type(&self) -> HeaderValue {
+        HeaderValue::from_static(mime::APPLICATION_OCTET_STREAM.as_ref())
+    }
+
+    fn body(self) -> Result<Self::Body, BodyError> {
+        Ok(Full::new(self.into()))
+    }
+}
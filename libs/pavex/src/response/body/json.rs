@@ -0,0 +1,51 @@
+use bytes::Bytes;
+use http_body_util::Full;
+use serde::Serialize;
+
+use crate::http::HeaderValue;
+
+use super::{BodyError, TypedBody};
+
+/// A [`TypedBody`] that serializes its wrapped value as JSON, using [`serde_json`], and sets
+/// the `Content-Type` header to `application/json`.
+///
+/// # Example
+///
+/// ```rust
+/// use pavex::response::body::Json;
+/// use serde::Serialize;
+///
+/// #[derive(Serialize)]
+/// struct Greeting {
+///     message: String,
+/// }
+///
+/// # fn t() -> Json<Greeting> {
+/// Json(Greeting { message: "Hello, world!".into() })
+/// # }
+/// ```
+///
+/// # Serialization errors
+///
+/// A `T` that fails to serialize is surfaced as an `Err` from [`TypedBody::body`], instead of
+/// either panicking (which would abort every other request multiplexed on the same connection)
+/// or silently downgrading the failure into a `200` response with a made-up body. Whoever builds
+/// a [`Response`](crate::response::Response) from a [`Json`] body is expected to turn that `Err`
+/// into a `500`.
+pub struct Json<T>(pub T);
+
+impl<T> TypedBody for Json<T>
+where
+    T: Serialize,
+{
+    type Body = Full<Bytes>;
+
+    fn content_type(&self) -> HeaderValue {
+        HeaderValue::from_static(mime::APPLICATION_JSON.as_ref())
+    }
+
+    fn body(self) -> Result<Self::Body, BodyError> {
+        let bytes = serde_json::to_vec(&self.0).map_err(BodyError::new)?;
+        Ok(Full::new(Bytes::from(bytes)))
+    }
+}
@@ -0,0 +1,64 @@
+//! Strongly-typed response bodies.
+//!
+//! Check out [`TypedBody`] for more details.
+use std::fmt;
+
+use bytes::Bytes;
+
+use crate::http::HeaderValue;
+
+mod byte_body;
+mod json;
+mod plain_text;
+mod stream;
+
+pub use json::Json;
+pub use stream::{FrameStream, StreamBody};
+
+/// A type that can be converted into an HTTP response body, together with the `Content-Type`
+/// header that describes it.
+///
+/// Pavex implements [`TypedBody`] for a handful of common types out of the box—strings, byte
+/// buffers, streams (see [`StreamBody`]) and JSON-serializable values (see [`Json`])—so that
+/// your request handlers can return them directly instead of having to build an
+/// [`http_body`] body by hand.
+pub trait TypedBody {
+    /// The concrete [`http_body::Body`] that [`TypedBody::body`] produces.
+    type Body: http_body::Body<Data = Bytes> + Send + 'static;
+
+    /// The value for the `Content-Type` header that should be attached to the response built
+    /// from this body.
+    fn content_type(&self) -> HeaderValue;
+
+    /// Convert `self` into the [`http_body::Body`] that will be streamed back to the client.
+    ///
+    /// Most implementations can't actually fail and always return `Ok`. A [`Json`] wrapping a
+    /// value whose [`Serialize`](serde::Serialize) implementation fails is the exception:
+    /// whoever builds a [`Response`](crate::response::Response) from a [`TypedBody`] is
+    /// expected to turn an `Err` here into a `500`, instead of the body silently lying about
+    /// what was actually sent back.
+    fn body(self) -> Result<Self::Body, BodyError>;
+}
+
+/// The error produced when a [`TypedBody`] fails to construct its underlying body—see
+/// [`TypedBody::body`].
+#[derive(Debug)]
+pub struct BodyError(Box<dyn std::error::Error + Send + Sync>);
+
+impl BodyError {
+    pub(crate) fn new(source: impl std::error::Error + Send + Sync + 'static) -> Self {
+        Self(Box::new(source))
+    }
+}
+
+impl fmt::Display for BodyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "failed to construct a response body: {}", self.0)
+    }
+}
+
+impl std::error::Error for BodyError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(self.0.as_ref())
+    }
+}
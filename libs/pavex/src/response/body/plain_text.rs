@@ -5,7 +5,7 @@ use http_body_util::Full;
 
 use crate::http::HeaderValue;
 
-use super::TypedBody;
+use super::{BodyError, TypedBody};
 
 impl TypedBody for String {
     type Body = Full<Bytes>;
@@ -14,8 +14,8 @@ impl TypedBody for String {
         HeaderValue::from_static(mime::TEXT_PLAIN_UTF_8.as_ref())
     }
 
-    fn body(self) -> Self::Body {
-        Full::new(self.into())
+    fn body(self) -> Result<Self::Body, BodyError> {
+        Ok(Full::new(self.into()))
     }
 }
 
@@ -26,8 +26,8 @@ impl TypedBody for &'static str {
         HeaderValue::from_static(mime::TEXT_PLAIN_UTF_8.as_ref())
     }
 
-    fn body(self) -> Self::Body {
-        Full::new(self.into())
+    fn body(self) -> Result<Self::Body, BodyError> {
+        Ok(Full::new(self.into()))
     }
 }
 
@@ -38,7 +38,7 @@ impl TypedBody for Cow<'static, str> {
         HeaderValue::from_static(mime::TEXT_PLAIN_UTF_8.as_ref())
     }
 
-    fn body(self) -> Self::Body {
+    fn body(self) -> Result<Self::Body, BodyError> {
         match self {
             Cow::Borrowed(s) => s.body(),
             Cow::Owned(s) => s.body(),
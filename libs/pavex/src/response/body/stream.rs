@@ -0,0 +1,69 @@
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use bytes::Bytes;
+use futures_util::Stream;
+use http_body::Frame;
+
+use crate::http::HeaderValue;
+
+use super::{BodyError, TypedBody};
+
+/// A [`TypedBody`] that streams its response body from a [`Stream`] of byte chunks, instead of
+/// buffering the whole thing in memory up front—handy for large or unbounded responses (e.g.
+/// file downloads or server-sent events).
+///
+/// # Example
+///
+/// ```rust
+/// use bytes::Bytes;
+/// use pavex::response::body::StreamBody;
+///
+/// # fn t(chunks: impl futures_util::Stream<Item = Result<Bytes, std::io::Error>> + Unpin + Send + 'static) {
+/// let body = StreamBody::new(chunks);
+/// # }
+/// ```
+pub struct StreamBody<S> {
+    stream: S,
+}
+
+impl<S> StreamBody<S> {
+    /// Wrap a [`Stream`] of byte chunks into a [`StreamBody`].
+    pub fn new(stream: S) -> Self {
+        Self { stream }
+    }
+}
+
+impl<S, E> TypedBody for StreamBody<S>
+where
+    S: Stream<Item = Result<Bytes, E>> + Unpin + Send + 'static,
+    E: 'static,
+{
+    type Body = http_body_util::StreamBody<FrameStream<S>>;
+
+    fn content_type(&self) -> HeaderValue {
+        HeaderValue::from_static(mime::APPLICATION_OCTET_STREAM.as_ref())
+    }
+
+    fn body(self) -> Result<Self::Body, BodyError> {
+        Ok(http_body_util::StreamBody::new(FrameStream(self.stream)))
+    }
+}
+
+/// Adapts a [`Stream`] of raw byte chunks into the `Stream` of [`Frame`]s that
+/// [`http_body_util::StreamBody`] expects.
+pub struct FrameStream<S>(S);
+
+impl<S, E> Stream for FrameStream<S>
+where
+    S: Stream<Item = Result<Bytes, E>> + Unpin,
+{
+    type Item = Result<Frame<Bytes>, E>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        Pin::new(&mut this.0)
+            .poll_next(cx)
+            .map(|item| item.map(|chunk| chunk.map(Frame::data)))
+    }
+}
@@ -0,0 +1,92 @@
+use std::num::NonZeroUsize;
+
+/// Configuration options for a [`Server`](super::Server).
+///
+/// # Example
+///
+/// ```rust
+/// use pavex::server::ServerConfiguration;
+///
+/// let config = ServerConfiguration::new()
+///     .n_workers(4.try_into().unwrap());
+/// ```
+#[derive(Clone, Debug)]
+pub struct ServerConfiguration {
+    n_workers: NonZeroUsize,
+    max_connections: Option<usize>,
+    max_connections_per_second: Option<NonZeroUsize>,
+}
+
+impl Default for ServerConfiguration {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ServerConfiguration {
+    /// Create a new [`ServerConfiguration`] with default values.
+    ///
+    /// By default, a [`Server`](super::Server) spawns one worker per available CPU core.
+    pub fn new() -> Self {
+        let n_workers = std::thread::available_parallelism().unwrap_or(NonZeroUsize::new(1).unwrap());
+        Self {
+            n_workers,
+            max_connections: None,
+            max_connections_per_second: None,
+        }
+    }
+
+    /// Set the number of workers that the [`Server`](super::Server) will spawn to handle
+    /// incoming connections.
+    ///
+    /// Each worker runs on its own OS thread, with a dedicated single-threaded [`tokio`]
+    /// runtime.
+    pub fn n_workers(mut self, n_workers: NonZeroUsize) -> Self {
+        self.n_workers = n_workers;
+        self
+    }
+
+    /// The number of workers that the [`Server`](super::Server) will spawn to handle
+    /// incoming connections.
+    pub fn get_n_workers(&self) -> NonZeroUsize {
+        self.n_workers
+    }
+
+    /// Cap the number of connections that the [`Server`](super::Server) will keep alive at
+    /// any given time, across all workers.
+    ///
+    /// Once this many connections are live, the acceptor stops polling its listeners for new
+    /// ones—existing connections are left untouched—until the live count drops to or below a
+    /// low watermark (10 below `max_connections`, or zero if `max_connections` is 10 or less),
+    /// to avoid thrashing between accepting and pausing on every single connection close.
+    ///
+    /// Unset by default, i.e. no cap.
+    pub fn max_connections(mut self, max_connections: usize) -> Self {
+        self.max_connections = Some(max_connections);
+        self
+    }
+
+    /// The configured cap on the number of live connections, if any—see
+    /// [`ServerConfiguration::max_connections`].
+    pub fn get_max_connections(&self) -> Option<usize> {
+        self.max_connections
+    }
+
+    /// Cap the rate at which the [`Server`](super::Server) accepts new connections, across all
+    /// listeners.
+    ///
+    /// Once this many connections have been accepted within the current one-second window,
+    /// the acceptor sleeps for whatever is left of that window before accepting another one.
+    ///
+    /// Unset by default, i.e. no cap.
+    pub fn max_connections_per_second(mut self, max_connections_per_second: NonZeroUsize) -> Self {
+        self.max_connections_per_second = Some(max_connections_per_second);
+        self
+    }
+
+    /// The configured cap on the accept rate, if any—see
+    /// [`ServerConfiguration::max_connections_per_second`].
+    pub fn get_max_connections_per_second(&self) -> Option<NonZeroUsize> {
+        self.max_connections_per_second
+    }
+}
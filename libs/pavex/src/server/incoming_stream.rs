@@ -0,0 +1,145 @@
+use std::net::SocketAddr;
+use std::path::Path;
+use std::sync::Arc;
+
+/// The address a connection was accepted from.
+///
+/// TCP listeners (see [`IncomingStream::bind`]) expose a [`SocketAddr`].
+/// Unix domain socket listeners (see [`IncomingStream::bind_unix`]) instead expose a, usually
+/// unnamed, [`tokio::net::unix::SocketAddr`].
+#[derive(Clone, Debug)]
+pub enum PeerAddr {
+    Tcp(SocketAddr),
+    Unix(Arc<tokio::net::unix::SocketAddr>),
+}
+
+enum Listener {
+    Tcp(tokio::net::TcpListener),
+    Unix(tokio::net::UnixListener),
+}
+
+/// A connection accepted from an [`IncomingStream`], handed off as a `std` socket so that it
+/// can be sent to whichever worker thread ends up serving it—see
+/// [`Server::serve`](super::Server::serve) for the full picture.
+pub(super) enum AcceptedStream {
+    Tcp(std::net::TcpStream),
+    Unix(std::os::unix::net::UnixStream),
+}
+
+/// A stream of incoming connections, bound to a specific address.
+///
+/// Check out [`Server::listen`](super::Server::listen) and [`Server::bind`](super::Server::bind)
+/// for more details on how an [`IncomingStream`] is used.
+pub struct IncomingStream {
+    listener: Listener,
+}
+
+impl IncomingStream {
+    /// Bind a new [`IncomingStream`] to the given address.
+    ///
+    /// Binding may fail (e.g. if the address is already in use), therefore this method
+    /// returns a [`std::io::Result`].
+    pub async fn bind(addr: SocketAddr) -> std::io::Result<Self> {
+        let listener = tokio::net::TcpListener::bind(addr).await?;
+        Ok(Self {
+            listener: Listener::Tcp(listener),
+        })
+    }
+
+    /// Bind a new [`IncomingStream`] to the given Unix domain socket path.
+    ///
+    /// This is the Unix domain socket equivalent of [`IncomingStream::bind`]—useful when a
+    /// reverse proxy (e.g. nginx or Envoy) sits in front of Pavex and talks to it over a
+    /// socket file rather than a loopback TCP port.
+    ///
+    /// Binding fails if a file already exists at `path`; remove any stale socket file left
+    /// over from a previous run before calling this method.
+    pub async fn bind_unix(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let listener = tokio::net::UnixListener::bind(path)?;
+        Ok(Self {
+            listener: Listener::Unix(listener),
+        })
+    }
+
+    /// Returns the local address that this [`IncomingStream`] is bound to.
+    pub fn local_addr(&self) -> std::io::Result<PeerAddr> {
+        match &self.listener {
+            Listener::Tcp(listener) => listener.local_addr().map(PeerAddr::Tcp),
+            Listener::Unix(listener) => listener
+                .local_addr()
+                .map(|addr| PeerAddr::Unix(Arc::new(addr))),
+        }
+    }
+
+    /// Accept a single incoming connection, converting it to its `std` equivalent so that it
+    /// can be handed off to a worker thread.
+    pub(super) async fn accept(&self) -> std::io::Result<(AcceptedStream, PeerAddr)> {
+        match &self.listener {
+            Listener::Tcp(listener) => {
+                let (stream, addr) = listener.accept().await?;
+                Ok((AcceptedStream::Tcp(stream.into_std()?), PeerAddr::Tcp(addr)))
+            }
+            Listener::Unix(listener) => {
+                let (stream, addr) = listener.accept().await?;
+                Ok((
+                    AcceptedStream::Unix(stream.into_std()?),
+                    PeerAddr::Unix(Arc::new(addr)),
+                ))
+            }
+        }
+    }
+}
+
+impl TryFrom<std::net::TcpListener> for IncomingStream {
+    type Error = std::io::Error;
+
+    fn try_from(listener: std::net::TcpListener) -> Result<Self, Self::Error> {
+        listener.set_nonblocking(true)?;
+        let listener = tokio::net::TcpListener::from_std(listener)?;
+        Ok(Self {
+            listener: Listener::Tcp(listener),
+        })
+    }
+}
+
+impl TryFrom<std::os::unix::net::UnixListener> for IncomingStream {
+    type Error = std::io::Error;
+
+    fn try_from(listener: std::os::unix::net::UnixListener) -> Result<Self, Self::Error> {
+        listener.set_nonblocking(true)?;
+        let listener = tokio::net::UnixListener::from_std(listener)?;
+        Ok(Self {
+            listener: Listener::Unix(listener),
+        })
+    }
+}
+
+/// Whether a [`BoundListener`] expects a TLS handshake before the connection can be
+/// treated as HTTP.
+pub(super) enum ListenerKind {
+    Plain,
+    Tls(Arc<rustls::ServerConfig>),
+}
+
+/// An [`IncomingStream`] together with the information the acceptor thread needs to turn
+/// an accepted socket into an HTTP connection.
+///
+/// `handler` is `None` for listeners registered through [`Server::bind`](super::Server::bind),
+/// [`Server::listen`](super::Server::listen) and their TLS equivalents—
+/// [`Server::serve`](super::Server::serve) fills it in with the default handler for every listener that
+/// doesn't already have one attached via [`Server::serve_on`](super::Server::serve_on).
+pub(super) struct BoundListener {
+    pub(super) incoming: IncomingStream,
+    pub(super) kind: ListenerKind,
+    pub(super) handler: Option<super::worker::Handler>,
+}
+
+impl From<IncomingStream> for BoundListener {
+    fn from(incoming: IncomingStream) -> Self {
+        Self {
+            incoming,
+            kind: ListenerKind::Plain,
+            handler: None,
+        }
+    }
+}
@@ -0,0 +1,13 @@
+//! Configure and launch the HTTP server that powers a Pavex application.
+//!
+//! Check out [`Server`] for more details.
+mod configuration;
+mod incoming_stream;
+mod server;
+mod server_handle;
+mod worker;
+
+pub use configuration::ServerConfiguration;
+pub use incoming_stream::{IncomingStream, PeerAddr};
+pub use server::Server;
+pub use server_handle::ServerHandle;
@@ -1,9 +1,13 @@
 use std::future::Future;
 use std::net::SocketAddr;
+use std::path::Path;
+use std::sync::Arc;
 
 use crate::server::configuration::ServerConfiguration;
 use crate::server::server_handle::ServerHandle;
 
+use super::incoming_stream::{BoundListener, ListenerKind};
+use super::worker::erase_handler;
 use super::IncomingStream;
 
 /// An HTTP server to handle incoming connections for Pavex applications.  
@@ -55,7 +59,7 @@ use super::IncomingStream;
 #[must_use = "You must call `serve` on a `Server` to start listening for incoming connections"]
 pub struct Server {
     config: ServerConfiguration,
-    incoming: Vec<IncomingStream>,
+    listeners: Vec<BoundListener>,
 }
 
 impl Default for Server {
@@ -69,7 +73,7 @@ impl Server {
     pub fn new() -> Self {
         Self {
             config: ServerConfiguration::default(),
-            incoming: Vec::new(),
+            listeners: Vec::new(),
         }
     }
 
@@ -145,7 +149,50 @@ impl Server {
     /// ````
     pub async fn bind(mut self, addr: SocketAddr) -> std::io::Result<Self> {
         let incoming = IncomingStream::bind(addr).await?;
-        self.incoming.push(incoming);
+        self.listeners.push(incoming.into());
+        Ok(self)
+    }
+
+    /// Bind the server to the given Unix domain socket path: the server will accept incoming
+    /// connections from this socket when started.
+    ///
+    /// This is the Unix domain socket equivalent of [`Server::bind`]—useful when a reverse
+    /// proxy (e.g. nginx or Envoy) sits in front of Pavex and talks to it over a socket file
+    /// rather than a loopback TCP port.
+    ///
+    /// Binding fails if a file already exists at `path`; remove any stale socket file left
+    /// over from a previous run before calling this method.
+    pub async fn bind_unix(mut self, path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let incoming = IncomingStream::bind_unix(path).await?;
+        self.listeners.push(incoming.into());
+        Ok(self)
+    }
+
+    /// Bind the server to the given address and terminate TLS on every connection accepted
+    /// from it, using the provided `tls_config`.
+    ///
+    /// The `tls_config`'s ALPN protocols are overwritten with `h2` and `http/1.1` so that
+    /// HTTP/2 is negotiated over TLS exactly like it already is over cleartext connections—
+    /// you don't need to (and shouldn't) set `alpn_protocols` yourself.
+    ///
+    /// Binding an address may fail (e.g. if the address is already in use), therefore this
+    /// method may return an error.
+    ///
+    /// # Related
+    ///
+    /// Check out [`Server::listen_tls`] if you need full control over the underlying socket,
+    /// e.g. to set a custom backlog—see [`Server::listen`] for the cleartext equivalent.
+    pub async fn bind_tls(
+        mut self,
+        addr: SocketAddr,
+        tls_config: rustls::ServerConfig,
+    ) -> std::io::Result<Self> {
+        let incoming = IncomingStream::bind(addr).await?;
+        self.listeners.push(BoundListener {
+            incoming,
+            kind: ListenerKind::Tls(Self::with_h2_alpn(tls_config)),
+            handler: None,
+        });
         Ok(self)
     }
 
@@ -224,10 +271,82 @@ impl Server {
     /// A [`Server`] can listen to multiple streams of incoming connections: just call this method
     /// multiple times!
     pub fn listen(mut self, incoming: IncomingStream) -> Self {
-        self.incoming.push(incoming);
+        self.listeners.push(incoming.into());
         self
     }
 
+    /// Ask the server to terminate TLS, using the provided `tls_config`, on every connection
+    /// accepted from the provided [`IncomingStream`].
+    ///
+    /// See [`Server::bind_tls`] for more details, including the note on ALPN protocols.
+    pub fn listen_tls(mut self, incoming: IncomingStream, tls_config: rustls::ServerConfig) -> Self {
+        self.listeners.push(BoundListener {
+            incoming,
+            kind: ListenerKind::Tls(Self::with_h2_alpn(tls_config)),
+            handler: None,
+        });
+        self
+    }
+
+    /// Attach a dedicated handler function and application state to the provided
+    /// [`IncomingStream`], instead of the default one passed to [`Server::serve`].
+    ///
+    /// This is how a single [`Server`] exposes more than one routing function at once—e.g. a
+    /// public API on one address and an internal admin/metrics surface on another, each with
+    /// its own application state—mirroring the multi-service-per-listener pattern from
+    /// [`hyper`'s `multi_server` example](https://github.com/hyperium/hyper/blob/master/examples/multi_server.rs).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::net::SocketAddr;
+    /// use pavex::server::{IncomingStream, Server};
+    ///
+    /// # #[derive(Clone)] struct ApplicationState;
+    /// # #[derive(Clone)] struct AdminState;
+    /// # async fn router(_req: hyper::Request<hyper::body::Incoming>, _state: ApplicationState) -> pavex::response::Response { todo!() }
+    /// # async fn admin_router(_req: hyper::Request<hyper::body::Incoming>, _state: AdminState) -> pavex::response::Response { todo!() }
+    /// # async fn t() -> std::io::Result<()> {
+    /// let public_addr = SocketAddr::from(([127, 0, 0, 1], 8080));
+    /// let admin_addr = SocketAddr::from(([127, 0, 0, 1], 9000));
+    /// let admin_incoming = IncomingStream::bind(admin_addr).await?;
+    ///
+    /// Server::new()
+    ///     .bind(public_addr)
+    ///     .await?
+    ///     .serve_on(admin_incoming, admin_router, AdminState)
+    ///     // The public API, registered via `bind` above, uses the handler and state
+    ///     // passed to `serve` below; the admin listener uses `admin_router` instead.
+    ///     .serve(router, ApplicationState)
+    ///     .await;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn serve_on<HandlerFuture, ApplicationState>(
+        mut self,
+        incoming: IncomingStream,
+        handler: fn(http::Request<hyper::body::Incoming>, ApplicationState) -> HandlerFuture,
+        application_state: ApplicationState,
+    ) -> Self
+    where
+        HandlerFuture: Future<Output = crate::response::Response> + Send + 'static,
+        ApplicationState: Clone + Send + Sync + 'static,
+    {
+        self.listeners.push(BoundListener {
+            incoming,
+            kind: ListenerKind::Plain,
+            handler: Some(erase_handler(handler, application_state)),
+        });
+        self
+    }
+
+    /// Overwrite `tls_config`'s ALPN protocols with `h2` and `http/1.1`, then wrap it in the
+    /// [`Arc`] that every accepted TLS connection will share.
+    fn with_h2_alpn(mut tls_config: rustls::ServerConfig) -> Arc<rustls::ServerConfig> {
+        tls_config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+        Arc::new(tls_config)
+    }
+
     /// Start listening for incoming connections.
     ///
     /// You must specify:
@@ -239,9 +358,12 @@ impl Server {
     /// Both the handler function and the application state are usually code-generated by Pavex
     /// starting from your [`Blueprint`](crate::blueprint::Blueprint).
     ///
+    /// This handler is the *default*: any listener attached through [`Server::serve_on`] keeps
+    /// using the handler and application state it was given there instead.
+    ///
     /// # Wait for the server to shut down
     ///
-    /// `serve` returns a [`ServerHandle`].  
+    /// `serve` returns a [`ServerHandle`].
     /// Calling `.await` on the handle lets you wait until the server shuts down.
     ///
     /// # Panics
@@ -249,17 +371,21 @@ impl Server {
     /// This method will panic if the [`Server`] has no registered source of incoming connections,
     /// i.e. if you did not call [`Server::bind`] or [`Server::listen`] before calling `serve`.
     pub fn serve<HandlerFuture, ApplicationState>(
-        self,
+        mut self,
         handler: fn(http::Request<hyper::body::Incoming>, ApplicationState) -> HandlerFuture,
         application_state: ApplicationState,
     ) -> ServerHandle
     where
-        HandlerFuture: Future<Output = crate::response::Response> + 'static,
+        HandlerFuture: Future<Output = crate::response::Response> + Send + 'static,
         ApplicationState: Clone + Send + Sync + 'static,
     {
-        if self.incoming.is_empty() {
+        if self.listeners.is_empty() {
             panic!("Cannot serve: there is no source of incoming connections. Please call `bind` or `listen` on the server before calling `serve`.");
         }
-        ServerHandle::new(self.config, self.incoming, handler, application_state)
+        let default_handler = erase_handler(handler, application_state);
+        for listener in &mut self.listeners {
+            listener.handler.get_or_insert_with(|| default_handler.clone());
+        }
+        ServerHandle::new(self.config, self.listeners)
     }
 }
@@ -0,0 +1,404 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use tokio::sync::{oneshot, watch};
+
+use super::configuration::ServerConfiguration;
+use super::incoming_stream::{BoundListener, ListenerKind};
+use super::worker::{Connection, Worker};
+
+/// The state of the acceptor, shared with every per-listener accept loop through a
+/// [`watch`] channel.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum AcceptorState {
+    /// Keep accepting new connections.
+    Running,
+    /// Stop polling the listeners for new connections, but keep them open—accepting can be
+    /// resumed at any time.
+    Paused,
+    /// Stop polling the listeners and drop them. Terminal: there is no coming back from this
+    /// state.
+    Stopped,
+}
+
+/// A handle to a running [`Server`](super::Server).
+///
+/// It is returned by [`Server::serve`](super::Server::serve).
+/// Await it to wait until the server shuts down.
+#[must_use = "A `ServerHandle` does nothing unless awaited or otherwise used"]
+pub struct ServerHandle {
+    workers: Vec<Arc<Worker>>,
+    /// The number of connections currently alive across every worker—see
+    /// [`ServerConfiguration::max_connections`].
+    live_connections: Arc<AtomicUsize>,
+    /// Shared with every per-listener accept loop—see [`AcceptorState`].
+    acceptor_state: watch::Sender<AcceptorState>,
+    acceptor_thread: Option<std::thread::JoinHandle<()>>,
+    completion: oneshot::Receiver<()>,
+}
+
+impl ServerHandle {
+    /// Every entry in `listeners` is expected to already have a [`Handler`] attached—see
+    /// [`Server::serve`](super::Server::serve), the only caller, which fills in the default
+    /// handler for any listener that doesn't have a dedicated one from
+    /// [`Server::serve_on`](super::Server::serve_on).
+    pub(super) fn new(config: ServerConfiguration, listeners: Vec<BoundListener>) -> Self {
+        let live_connections = Arc::new(AtomicUsize::new(0));
+        let workers: Vec<Arc<Worker>> = (0..config.get_n_workers().get())
+            .map(|_| Arc::new(Worker::spawn(live_connections.clone())))
+            .collect();
+
+        let max_connections = config.get_max_connections();
+        let rate_limiter = config
+            .get_max_connections_per_second()
+            .map(|max| Arc::new(RateLimiter::new(max.get() as u32)));
+
+        let (completion_tx, completion_rx) = oneshot::channel();
+        let (acceptor_state, acceptor_state_rx) = watch::channel(AcceptorState::Running);
+        let round_robin = Arc::new(AtomicUsize::new(0));
+        let acceptor_workers = workers.clone();
+        let acceptor_live_connections = live_connections.clone();
+        let acceptor_thread = std::thread::spawn(move || {
+            let runtime = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .expect("Failed to build the tokio runtime for the acceptor thread");
+            runtime.block_on(async move {
+                let mut tasks = tokio::task::JoinSet::new();
+                for listener in listeners {
+                    let round_robin = round_robin.clone();
+                    let workers = acceptor_workers.clone();
+                    let acceptor_state_rx = acceptor_state_rx.clone();
+                    let live_connections = acceptor_live_connections.clone();
+                    let rate_limiter = rate_limiter.clone();
+                    tasks.spawn(accept_loop(
+                        listener,
+                        round_robin,
+                        workers,
+                        acceptor_state_rx,
+                        live_connections,
+                        max_connections,
+                        rate_limiter,
+                    ));
+                }
+                while tasks.join_next().await.is_some() {}
+            });
+            let _ = completion_tx.send(());
+        });
+
+        Self {
+            workers,
+            live_connections,
+            acceptor_state,
+            acceptor_thread: Some(acceptor_thread),
+            completion: completion_rx,
+        }
+    }
+
+    /// Temporarily stop accepting new connections.
+    ///
+    /// Every bound listener is left open—existing connections on the workers are left
+    /// completely untouched—but the acceptor stops polling them for new ones. Call
+    /// [`ServerHandle::resume`] to start accepting again.
+    ///
+    /// This is handy to shed load, or to pause traffic while a rolling config reload happens
+    /// behind the scenes.
+    ///
+    /// A no-op once [`ServerHandle::shutdown`] has been called—[`AcceptorState::Stopped`] is
+    /// terminal, so there is no pausing a server that is already shutting down.
+    pub fn pause(&self) {
+        self.acceptor_state.send_if_modified(|state| {
+            if *state == AcceptorState::Stopped {
+                return false;
+            }
+            *state = AcceptorState::Paused;
+            true
+        });
+    }
+
+    /// Resume accepting new connections after a call to [`ServerHandle::pause`].
+    ///
+    /// A no-op once [`ServerHandle::shutdown`] has been called—[`AcceptorState::Stopped`] is
+    /// terminal, so resuming can't resurrect a listener that is already mid-teardown.
+    pub fn resume(&self) {
+        self.acceptor_state.send_if_modified(|state| {
+            if *state == AcceptorState::Stopped {
+                return false;
+            }
+            *state = AcceptorState::Running;
+            true
+        });
+    }
+
+    /// Shut the server down gracefully.
+    ///
+    /// This will, in order:
+    ///
+    /// 1. Stop the acceptor from accepting new connections and close every bound listener—no
+    ///    new connection will be accepted after this point, freeing up the listening ports.
+    /// 2. Ask every worker to let its in-flight connections finish the request they're
+    ///    currently serving, but not start serving another one on the same connection
+    ///    (hyper's [graceful shutdown](https://docs.rs/hyper-util/latest/hyper_util/server/conn/auto/struct.Builder.html)).
+    /// 3. Wait up to `timeout` for every worker to report that it has no connection left to
+    ///    serve. Connections that are still alive once `timeout` elapses are aborted.
+    ///
+    /// The returned future resolves once every worker has either drained or been aborted.
+    pub async fn shutdown(&self, timeout: Duration) {
+        let _ = self.acceptor_state.send(AcceptorState::Stopped);
+        for worker in &self.workers {
+            worker.begin_drain();
+        }
+
+        let deadline = tokio::time::Instant::now() + timeout;
+        loop {
+            let live = self.live_connections.load(Ordering::Relaxed);
+            if live == 0 {
+                return;
+            }
+            if tokio::time::Instant::now() >= deadline {
+                tracing::warn!(
+                    live_connections = live,
+                    "Graceful shutdown timed out; aborting the connections still open"
+                );
+                for worker in &self.workers {
+                    worker.abort_remaining();
+                }
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+    }
+}
+
+/// The low watermark, below `max_connections`, that the live connection count must drop under
+/// before the acceptor resumes polling its listeners—this avoids thrashing between accepting
+/// and pausing every time a single connection closes while right at the limit.
+const LOW_WATERMARK_GAP: usize = 10;
+
+/// Accept connections from a single listener, round-robining them across workers, until the
+/// [`AcceptorState`] becomes [`AcceptorState::Stopped`].
+///
+/// While the state is [`AcceptorState::Paused`]—or while `max_connections` has been reached—
+/// the listener is simply not polled for new connections; existing ones are left alone.
+#[allow(clippy::too_many_arguments)]
+async fn accept_loop(
+    listener: BoundListener,
+    round_robin: Arc<AtomicUsize>,
+    workers: Vec<Arc<Worker>>,
+    mut state: watch::Receiver<AcceptorState>,
+    live_connections: Arc<AtomicUsize>,
+    max_connections: Option<usize>,
+    rate_limiter: Option<Arc<RateLimiter>>,
+) {
+    let BoundListener {
+        incoming,
+        kind,
+        handler,
+    } = listener;
+    let handler = handler.expect(
+        "BoundListener reached the acceptor loop without a handler attached; `Server::serve` \
+         is expected to fill in the default handler for every listener before this point",
+    );
+    // Sticky: once we pause because `max_connections` was reached, we only resume once the
+    // live count drops under the low watermark, not as soon as it dips by one.
+    let mut capacity_paused = false;
+    loop {
+        loop {
+            if *state.borrow() == AcceptorState::Stopped {
+                return;
+            }
+            if let Some(max_connections) = max_connections {
+                let live = live_connections.load(Ordering::Relaxed);
+                capacity_paused = next_capacity_paused(capacity_paused, live, max_connections);
+            }
+            if *state.borrow() != AcceptorState::Paused && !capacity_paused {
+                break;
+            }
+            tokio::select! {
+                result = state.changed() => {
+                    if result.is_err() {
+                        return;
+                    }
+                }
+                // Only poll for the live count to drop while we're capacity-paused: when
+                // merely `Paused` by the operator there is nothing to wait for besides
+                // `state.changed()`.
+                _ = tokio::time::sleep(Duration::from_millis(20)), if capacity_paused => {}
+            }
+        }
+
+        // Count the upcoming accept against the rate limit before making it, so that once the
+        // window's allowance is used up we sleep before accepting another connection.
+        if let Some(rate_limiter) = &rate_limiter {
+            if let Some(remaining) = rate_limiter.record_accept() {
+                tokio::time::sleep(remaining).await;
+            }
+        }
+
+        let accepted = tokio::select! {
+            accepted = incoming.accept() => accepted,
+            result = state.changed() => {
+                if result.is_err() {
+                    return;
+                }
+                continue;
+            }
+        };
+        let (stream, peer_addr) = match accepted {
+            Ok(accepted) => accepted,
+            Err(e) => {
+                tracing::warn!(error = %e, "Failed to accept an incoming connection");
+                continue;
+            }
+        };
+        let connection = match &kind {
+            ListenerKind::Plain => Connection::Plain(stream, peer_addr, handler.clone()),
+            ListenerKind::Tls(tls_config) => {
+                Connection::Tls(stream, tls_config.clone(), peer_addr, handler.clone())
+            }
+        };
+        let i = round_robin.fetch_add(1, Ordering::Relaxed) % workers.len();
+        workers[i].dispatch(connection);
+    }
+}
+
+/// Decide whether the acceptor should be capacity-paused, given whether it already was and the
+/// current live connection count—pulled out of [`accept_loop`] so the sticky low-watermark
+/// hysteresis can be unit tested without spinning up real listeners.
+///
+/// Resuming requires dropping to (or under) the low watermark (`max_connections -
+/// LOW_WATERMARK_GAP`), not just under `max_connections`—see the "sticky" comment on
+/// [`accept_loop`]'s `capacity_paused` local for why.
+///
+/// The comparison has to be `<=`, not `<`: when `max_connections <= LOW_WATERMARK_GAP`, the
+/// watermark saturates to `0`, and `live < 0` can never hold for a `usize`—with a strict `<`
+/// the acceptor would become capacity-paused forever, since there would be no `live` value left
+/// that counts as "under" the watermark.
+fn next_capacity_paused(currently_paused: bool, live: usize, max_connections: usize) -> bool {
+    let low_watermark = max_connections.saturating_sub(LOW_WATERMARK_GAP);
+    if currently_paused && live <= low_watermark {
+        false
+    } else if !currently_paused && live >= max_connections {
+        true
+    } else {
+        currently_paused
+    }
+}
+
+/// A simple fixed-window rate limiter, shared across every listener's accept loop.
+struct RateLimiter {
+    max_per_second: u32,
+    window: std::sync::Mutex<(tokio::time::Instant, u32)>,
+}
+
+impl RateLimiter {
+    fn new(max_per_second: u32) -> Self {
+        Self {
+            max_per_second,
+            window: std::sync::Mutex::new((tokio::time::Instant::now(), 0)),
+        }
+    }
+
+    /// Record an accept; if that pushes the current one-second window over the limit, return
+    /// how long the caller should sleep before accepting again.
+    fn record_accept(&self) -> Option<Duration> {
+        let mut window = self.window.lock().unwrap();
+        let now = tokio::time::Instant::now();
+        if now.duration_since(window.0) >= Duration::from_secs(1) {
+            *window = (now, 0);
+        }
+        window.1 += 1;
+        if window.1 > self.max_per_second {
+            Some(Duration::from_secs(1).saturating_sub(now.duration_since(window.0)))
+        } else {
+            None
+        }
+    }
+}
+
+impl Future for ServerHandle {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match Pin::new(&mut self.completion).poll(cx) {
+            Poll::Ready(_) => {
+                if let Some(thread) = self.acceptor_thread.take() {
+                    let _ = thread.join();
+                }
+                Poll::Ready(())
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn capacity_pause_engages_at_max_connections() {
+        assert!(!next_capacity_paused(false, 9, 10));
+        assert!(next_capacity_paused(false, 10, 10));
+        assert!(next_capacity_paused(false, 11, 10));
+    }
+
+    #[test]
+    fn capacity_pause_is_sticky_until_the_low_watermark() {
+        // `LOW_WATERMARK_GAP` is 10, so with `max_connections = 10` the low watermark saturates
+        // to 0—resuming only once every connection has drained.
+        assert!(next_capacity_paused(true, 1, 10));
+        assert!(!next_capacity_paused(true, 0, 10));
+    }
+
+    #[test]
+    fn capacity_pause_resumes_under_the_low_watermark() {
+        // With a wider margin between `max_connections` and `LOW_WATERMARK_GAP`, the low
+        // watermark sits strictly above zero.
+        assert!(next_capacity_paused(true, 95, 100));
+        assert!(!next_capacity_paused(true, 89, 100));
+    }
+
+    #[test]
+    fn capacity_pause_leaves_an_already_correct_state_untouched() {
+        // Still below `max_connections` and not yet paused—stays unpaused.
+        assert!(!next_capacity_paused(false, 50, 100));
+        // Still above the low watermark and already paused—stays paused.
+        assert!(next_capacity_paused(true, 95, 100));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn rate_limiter_allows_up_to_the_per_second_cap() {
+        let limiter = RateLimiter::new(3);
+        assert!(limiter.record_accept().is_none());
+        assert!(limiter.record_accept().is_none());
+        assert!(limiter.record_accept().is_none());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn rate_limiter_returns_the_remaining_window_once_over_the_cap() {
+        let limiter = RateLimiter::new(1);
+        assert!(limiter.record_accept().is_none());
+
+        let remaining = limiter
+            .record_accept()
+            .expect("the second accept within the same window should be throttled");
+        assert!(remaining <= Duration::from_secs(1));
+        assert!(remaining > Duration::ZERO);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn rate_limiter_resets_once_the_window_elapses() {
+        let limiter = RateLimiter::new(1);
+        assert!(limiter.record_accept().is_none());
+        assert!(limiter.record_accept().is_some());
+
+        tokio::time::advance(Duration::from_secs(1)).await;
+
+        assert!(limiter.record_accept().is_none());
+    }
+}
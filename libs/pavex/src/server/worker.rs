@@ -0,0 +1,305 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+
+use hyper_util::rt::{TokioExecutor, TokioIo};
+use hyper_util::server::conn::auto::Builder as ConnectionBuilder;
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use tokio::net::{TcpStream, UnixStream};
+use tokio::sync::{mpsc, watch};
+use tokio::task::AbortHandle;
+
+use crate::response::Response;
+
+use super::incoming_stream::{AcceptedStream, PeerAddr};
+
+/// A type-erased request handler, bundled together with the application state it closes over.
+///
+/// Each [`BoundListener`](super::incoming_stream::BoundListener) carries its own [`Handler`]—
+/// see [`Server::serve_on`](super::Server::serve_on)—so that a single [`Server`](super::Server)
+/// can dispatch connections from different listeners to completely independent routing
+/// functions, each with its own application state type.
+pub(super) type Handler = Arc<
+    dyn Fn(http::Request<hyper::body::Incoming>) -> Pin<Box<dyn Future<Output = Response> + Send>>
+        + Send
+        + Sync,
+>;
+
+/// Type-erase a handler function together with the application state it closes over, so that
+/// the result can be attached to a listener and carried across to whichever worker ends up
+/// serving a connection accepted from it.
+pub(super) fn erase_handler<HandlerFuture, ApplicationState>(
+    handler: fn(http::Request<hyper::body::Incoming>, ApplicationState) -> HandlerFuture,
+    application_state: ApplicationState,
+) -> Handler
+where
+    HandlerFuture: Future<Output = Response> + Send + 'static,
+    ApplicationState: Clone + Send + Sync + 'static,
+{
+    Arc::new(move |request| {
+        let application_state = application_state.clone();
+        Box::pin(handler(request, application_state))
+    })
+}
+
+/// A connection accepted by the acceptor thread, waiting to be served by a [`Worker`].
+///
+/// It carries enough information for the worker to know how the raw [`AcceptedStream`] should
+/// be turned into an HTTP connection—in particular, whether a TLS handshake has to happen
+/// first—the [`PeerAddr`] it was accepted from, and the [`Handler`] of the listener it came
+/// from.
+pub(super) enum Connection {
+    Plain(AcceptedStream, PeerAddr, Handler),
+    Tls(AcceptedStream, Arc<rustls::ServerConfig>, PeerAddr, Handler),
+}
+
+/// Either kind of accepted connection (TCP or Unix domain socket), turned back into its `tokio` type on the worker
+/// that ends up serving it—see [`AcceptedStream`] for why it has to make this round trip
+/// through a `std` type in between.
+///
+/// [`tokio::net::TcpStream`] and [`tokio::net::UnixStream`] are different concrete types, so
+/// this enum—implementing [`AsyncRead`]/[`AsyncWrite`] by delegating to whichever variant is
+/// active—is what lets the rest of the worker treat both uniformly.
+enum AnyStream {
+    Tcp(TcpStream),
+    Unix(UnixStream),
+}
+
+impl AnyStream {
+    fn from_accepted(stream: AcceptedStream) -> std::io::Result<Self> {
+        match stream {
+            AcceptedStream::Tcp(stream) => {
+                let stream = TcpStream::from_std(stream)?;
+                let _ = stream.set_nodelay(true);
+                Ok(Self::Tcp(stream))
+            }
+            AcceptedStream::Unix(stream) => Ok(Self::Unix(UnixStream::from_std(stream)?)),
+        }
+    }
+}
+
+impl AsyncRead for AnyStream {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        match self.get_mut() {
+            Self::Tcp(stream) => Pin::new(stream).poll_read(cx, buf),
+            Self::Unix(stream) => Pin::new(stream).poll_read(cx, buf),
+        }
+    }
+}
+
+impl AsyncWrite for AnyStream {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<std::io::Result<usize>> {
+        match self.get_mut() {
+            Self::Tcp(stream) => Pin::new(stream).poll_write(cx, buf),
+            Self::Unix(stream) => Pin::new(stream).poll_write(cx, buf),
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        match self.get_mut() {
+            Self::Tcp(stream) => Pin::new(stream).poll_flush(cx),
+            Self::Unix(stream) => Pin::new(stream).poll_flush(cx),
+        }
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        match self.get_mut() {
+            Self::Tcp(stream) => Pin::new(stream).poll_shutdown(cx),
+            Self::Unix(stream) => Pin::new(stream).poll_shutdown(cx),
+        }
+    }
+}
+
+/// Increments `live_connections` on construction and decrements it on drop—including when the
+/// task holding it panics and unwinds, or is aborted by [`Worker::abort_remaining`]—so that a
+/// panicking handler can't permanently leak a slot out of
+/// [`ServerConfiguration::max_connections`](super::ServerConfiguration::max_connections).
+struct LiveConnectionGuard(Arc<AtomicUsize>);
+
+impl LiveConnectionGuard {
+    fn new(live_connections: Arc<AtomicUsize>) -> Self {
+        live_connections.fetch_add(1, Ordering::Relaxed);
+        Self(live_connections)
+    }
+}
+
+impl Drop for LiveConnectionGuard {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// A single worker: an OS thread running its own single-threaded [`tokio`] runtime, dedicated
+/// to serving the connections that the acceptor thread hands to it.
+pub(super) struct Worker {
+    sender: mpsc::UnboundedSender<Connection>,
+    thread: std::thread::JoinHandle<()>,
+    drain_tx: watch::Sender<bool>,
+    /// Keyed by a per-worker monotonic id rather than just a `Vec`, so that a connection can
+    /// remove its own entry once it finishes—otherwise this would grow for as long as the
+    /// worker is alive, not just while connections are in flight.
+    in_flight: Arc<Mutex<HashMap<u64, AbortHandle>>>,
+}
+
+impl Worker {
+    /// Spawn a new [`Worker`] thread.
+    ///
+    /// `live_connections` is shared by every worker: each one increments it when it starts
+    /// serving a connection and decrements it when that connection closes, so that the
+    /// acceptor thread can read the total live count across the whole [`Server`](super::Server)
+    /// to enforce [`ServerConfiguration::max_connections`](super::ServerConfiguration::max_connections).
+    ///
+    /// The [`Handler`] to invoke for a given connection travels with that connection (see
+    /// [`Connection`]), rather than being fixed at spawn time—this is what lets different
+    /// listeners on the same [`Server`](super::Server) dispatch to different handlers.
+    pub(super) fn spawn(live_connections: Arc<AtomicUsize>) -> Self {
+        let (sender, mut receiver) = mpsc::unbounded_channel::<Connection>();
+        let (drain_tx, drain_rx) = watch::channel(false);
+        let in_flight = Arc::new(Mutex::new(HashMap::new()));
+
+        let task_in_flight = in_flight.clone();
+        let thread = std::thread::spawn(move || {
+            let runtime = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .expect("Failed to build the tokio runtime for a Pavex worker");
+            let local = tokio::task::LocalSet::new();
+            // Only this thread ever hands out ids, so a plain counter (no need for an `Arc`)
+            // is enough to keep them unique for the lifetime of the worker.
+            let mut next_task_id: u64 = 0;
+            local.block_on(&runtime, async move {
+                while let Some(connection) = receiver.recv().await {
+                    let mut drain_rx = drain_rx.clone();
+                    let live_guard = LiveConnectionGuard::new(live_connections.clone());
+                    let task_id = next_task_id;
+                    next_task_id += 1;
+                    let task_in_flight_cleanup = task_in_flight.clone();
+                    let task = tokio::task::spawn_local(async move {
+                        let _live_guard = live_guard;
+                        if let Err(e) = serve_connection(connection, &mut drain_rx).await {
+                            tracing::warn!(error = %e, "A connection was terminated by an error");
+                        }
+                        // Prune our own entry now that the connection is done, instead of
+                        // only ever clearing it in `abort_remaining` at shutdown—otherwise
+                        // this map would grow for as long as the worker is alive.
+                        task_in_flight_cleanup.lock().unwrap().remove(&task_id);
+                    });
+                    task_in_flight.lock().unwrap().insert(task_id, task.abort_handle());
+                }
+            });
+        });
+        Self {
+            sender,
+            thread,
+            drain_tx,
+            in_flight,
+        }
+    }
+
+    /// Hand a freshly accepted connection to this worker.
+    ///
+    /// If the worker has already shut down, the connection is silently dropped—there is
+    /// nothing else we can do with it at that point.
+    pub(super) fn dispatch(&self, connection: Connection) {
+        let _ = self.sender.send(connection);
+    }
+
+    /// Ask every connection currently being served by this worker to finish the in-flight
+    /// request (if any) and then close, instead of accepting further requests on the same
+    /// connection.
+    pub(super) fn begin_drain(&self) {
+        let _ = self.drain_tx.send(true);
+    }
+
+    /// Forcibly abort every connection still being served by this worker.
+    ///
+    /// This is only meant to be called after [`Worker::begin_drain`] and the resulting grace
+    /// period have both elapsed.
+    pub(super) fn abort_remaining(&self) {
+        for (_, handle) in self.in_flight.lock().unwrap().drain() {
+            handle.abort();
+        }
+    }
+}
+
+async fn serve_connection(
+    connection: Connection,
+    drain: &mut watch::Receiver<bool>,
+) -> std::io::Result<()> {
+    // `hyper-util`'s `auto` builder tells HTTP/1.1 from HTTP/2 by the bytes the client sends
+    // first, for TLS and cleartext connections alike; over TLS, the client picks which of the
+    // two to speak from the ALPN protocol negotiated during the handshake.
+    match connection {
+        Connection::Plain(stream, peer_addr, handler) => {
+            let stream = AnyStream::from_accepted(stream)?;
+            serve_io(TokioIo::new(stream), make_service(handler, peer_addr), drain).await
+        }
+        Connection::Tls(stream, tls_config, peer_addr, handler) => {
+            let stream = AnyStream::from_accepted(stream)?;
+            let acceptor = tokio_rustls::TlsAcceptor::from(tls_config);
+            let stream = acceptor.accept(stream).await?;
+            serve_io(TokioIo::new(stream), make_service(handler, peer_addr), drain).await
+        }
+    }
+}
+
+/// Build the [`hyper`] service for a single connection, making sure every request that comes
+/// through it carries the [`PeerAddr`] it was accepted from—mirrors how `axum`'s
+/// `ConnectInfo` extractor is threaded through [`http::Extensions`].
+fn make_service(
+    handler: Handler,
+    peer_addr: PeerAddr,
+) -> impl hyper::service::Service<
+    http::Request<hyper::body::Incoming>,
+    Response = Response,
+    Error = std::convert::Infallible,
+    Future = Pin<Box<dyn Future<Output = Result<Response, std::convert::Infallible>> + Send>>,
+> + Send
+       + 'static {
+    hyper::service::service_fn(move |mut request| {
+        let handler = handler.clone();
+        let peer_addr = peer_addr.clone();
+        Box::pin(async move {
+            request.extensions_mut().insert(peer_addr);
+            Ok::<_, std::convert::Infallible>(handler(request).await)
+        }) as Pin<Box<dyn Future<Output = Result<Response, std::convert::Infallible>> + Send>>
+    })
+}
+
+/// Drive a single HTTP connection to completion, switching into a graceful shutdown as soon
+/// as `drain` fires.
+async fn serve_io<IO, S>(io: IO, service: S, drain: &mut watch::Receiver<bool>) -> std::io::Result<()>
+where
+    IO: hyper::rt::Read + hyper::rt::Write + Unpin + 'static,
+    S: hyper::service::Service<
+            http::Request<hyper::body::Incoming>,
+            Response = Response,
+            Error = std::convert::Infallible,
+        > + Send
+        + 'static,
+    S::Future: Send,
+{
+    let conn = ConnectionBuilder::new(TokioExecutor::new()).serve_connection(io, service);
+    tokio::pin!(conn);
+    tokio::select! {
+        result = conn.as_mut() => result.map_err(std::io::Error::other),
+        // If the server starts draining while this connection is idle between requests, ask
+        // hyper to close it as soon as the current exchange (if any) is done, then keep
+        // polling the connection to let that happen.
+        _ = drain.changed() => {
+            conn.as_mut().graceful_shutdown();
+            conn.await.map_err(std::io::Error::other)
+        }
+    }
+}